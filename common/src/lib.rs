@@ -0,0 +1,17 @@
+//! 共享的交互式输入逻辑
+//!
+//! 被 `treehouse`（姓名门禁示例）和 `flappy`（启动时的姓名提示）复用，
+//! 避免同一段“读一行、去空白、转小写”的逻辑散落成多份拷贝。
+use std::io::stdin;
+
+/// 从标准输入读取一行姓名
+///
+/// 去除首尾空白并转换为小写，便于调用方做大小写无关的比较
+///
+/// # 返回值
+/// 返回读取到的姓名；输入为空行时返回空字符串
+pub fn read_name() -> String {
+    let mut name = String::new();
+    stdin().read_line(&mut name).expect("Failed to read line");
+    name.trim().to_lowercase()
+}
@@ -0,0 +1,220 @@
+//! 历史最高分持久化
+//!
+//! 将榜单保存为用户配置目录下的逗号分隔文本文件，与渲染循环解耦，
+//! 以便单独测试加载/记录逻辑。WebAssembly 构建没有真实文件系统，
+//! 因此该平台上只在内存里保留榜单，不做任何持久化。
+
+/// 单条历史最高分记录
+pub struct ScoreEntry {
+    /// 玩家姓名
+    pub name: String,
+    /// 取得的分数
+    pub score: i32,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::ScoreEntry;
+    use std::fs;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+
+    /// 历史最高分榜
+    pub struct HighScores {
+        /// 按分数从高到低排序的记录
+        entries: Vec<ScoreEntry>,
+    }
+
+    impl HighScores {
+        /// 榜单文件在用户配置目录下的路径
+        fn path() -> PathBuf {
+            let mut dir = dirs::config_dir().unwrap_or_else(std::env::temp_dir);
+            dir.push("flappy_dragon");
+            dir.push("highscores.txt");
+            dir
+        }
+
+        /// 从磁盘加载历史最高分
+        ///
+        /// 文件缺失或内容损坏时，从空榜单开始，而不是报错
+        ///
+        /// # 返回值
+        /// 返回一个按分数降序排列的 HighScores 实例
+        pub fn load() -> Self {
+            Self::load_from(&Self::path())
+        }
+
+        /// `load` 的实现，接受一个显式路径，便于在测试中指向临时文件
+        /// 而不是真实的用户配置目录
+        fn load_from(path: &Path) -> Self {
+            let mut entries: Vec<ScoreEntry> = fs::read_to_string(path)
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| {
+                    let (name, score) = line.rsplit_once(',')?;
+                    Some(ScoreEntry {
+                        name: name.to_string(),
+                        score: score.trim().parse().ok()?,
+                    })
+                })
+                .collect();
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+            HighScores { entries }
+        }
+
+        /// 记录一条新的分数并立即持久化到磁盘
+        ///
+        /// # 参数
+        /// * `name` - 玩家姓名
+        /// * `score` - 本局取得的分数
+        pub fn record(&mut self, name: &str, score: i32) {
+            self.entries.push(ScoreEntry {
+                name: name.to_string(),
+                score,
+            });
+            self.entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+            self.save();
+        }
+
+        /// 将当前榜单写回磁盘，忽略写入失败（例如只读文件系统）
+        fn save(&self) {
+            self.save_to(&Self::path());
+        }
+
+        /// `save` 的实现，接受一个显式路径，便于在测试中指向临时文件
+        /// 而不是真实的用户配置目录
+        fn save_to(&self, path: &Path) {
+            if let Some(parent) = path.parent() {
+                if fs::create_dir_all(parent).is_err() {
+                    return;
+                }
+            }
+            let contents: String = self
+                .entries
+                .iter()
+                .map(|entry| format!("{},{}\n", entry.name, entry.score))
+                .collect();
+            if let Ok(mut file) = fs::File::create(path) {
+                let _ = file.write_all(contents.as_bytes());
+            }
+        }
+
+        /// 返回分数最高的前 `n` 条记录
+        ///
+        /// # 参数
+        /// * `n` - 要返回的记录条数
+        pub fn top(&self, n: usize) -> &[ScoreEntry] {
+            &self.entries[..self.entries.len().min(n)]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// 返回一个进程内唯一的临时文件路径，避免并发测试互相踩踏
+        fn temp_path(label: &str) -> PathBuf {
+            let mut path = std::env::temp_dir();
+            path.push(format!(
+                "flappy_dragon_high_scores_test_{}_{}",
+                std::process::id(),
+                label
+            ));
+            path
+        }
+
+        #[test]
+        fn load_from_missing_file_returns_empty_board() {
+            let path = temp_path("missing");
+            let _ = fs::remove_file(&path);
+
+            let scores = HighScores::load_from(&path);
+
+            assert!(scores.top(10).is_empty());
+        }
+
+        #[test]
+        fn load_from_skips_corrupt_or_partial_lines() {
+            let path = temp_path("corrupt");
+            fs::write(&path, "alice,10\nnot a score line\nbob,\ncarol,30\n").unwrap();
+
+            let scores = HighScores::load_from(&path);
+            let names: Vec<&str> = scores.top(10).iter().map(|e| e.name.as_str()).collect();
+
+            assert_eq!(names, vec!["carol", "alice"]);
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn top_caps_at_available_entry_count() {
+            let scores = HighScores {
+                entries: vec![
+                    ScoreEntry {
+                        name: "a".to_string(),
+                        score: 3,
+                    },
+                    ScoreEntry {
+                        name: "b".to_string(),
+                        score: 1,
+                    },
+                ],
+            };
+
+            assert_eq!(scores.top(10).len(), 2);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::ScoreEntry;
+
+    /// 历史最高分榜（WebAssembly 空实现）
+    ///
+    /// 浏览器环境没有真实文件系统，榜单只保存在内存中，
+    /// 不会在页面刷新或会话之间持久化
+    pub struct HighScores {
+        /// 按分数从高到低排序的记录
+        entries: Vec<ScoreEntry>,
+    }
+
+    impl HighScores {
+        /// 返回一个空榜单
+        ///
+        /// # 返回值
+        /// 返回一个新的 HighScores 实例
+        pub fn load() -> Self {
+            HighScores {
+                entries: Vec::new(),
+            }
+        }
+
+        /// 记录一条新的分数（仅保留在内存中）
+        ///
+        /// # 参数
+        /// * `name` - 玩家姓名
+        /// * `score` - 本局取得的分数
+        pub fn record(&mut self, name: &str, score: i32) {
+            self.entries.push(ScoreEntry {
+                name: name.to_string(),
+                score,
+            });
+            self.entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        }
+
+        /// 返回分数最高的前 `n` 条记录
+        ///
+        /// # 参数
+        /// * `n` - 要返回的记录条数
+        pub fn top(&self, n: usize) -> &[ScoreEntry] {
+            &self.entries[..self.entries.len().min(n)]
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::HighScores;
+#[cfg(target_arch = "wasm32")]
+pub use web::HighScores;
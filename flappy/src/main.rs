@@ -1,14 +1,80 @@
 /// Flappy Dragon 游戏
 /// 一个简单的 Flappy Bird 风格游戏，使用 bracket-lib 实现。
-use bracket_lib::prelude::*;
+mod high_scores;
 
+use bracket_lib::prelude::*;
+use high_scores::HighScores;
+#[cfg(feature = "audio")]
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
+use std::collections::VecDeque;
+#[cfg(feature = "audio")]
+use std::io::Cursor;
 
 /// 游戏屏幕宽度
 const SCREEN_WIDTH: i32 = 80;
 /// 游戏屏幕高度
 const SCREEN_HEIGHT: i32 = 50;
-/// 帧持续时间（毫秒）
-const FRAME_DURATION: f32 = 60.0;
+/// 相邻障碍物之间的水平间距
+const OBSTACLE_SPACING: i32 = 25;
+/// 当最右侧障碍物与玩家的距离小于该阈值时，生成下一个障碍物
+const SPAWN_THRESHOLD: i32 = SCREEN_WIDTH;
+/// 龙扇动翅膀的动画帧（CP437 字形编号）
+const DRAGON_FRAMES: [u16; 6] = [64, 1, 2, 3, 2, 1];
+/// 动画每帧切换所需的累计帧时间（毫秒）
+const FLAP_FRAME_DURATION: f32 = 120.0;
+/// 在菜单和死亡界面展示的历史最高分条数
+const HIGH_SCORE_COUNT: usize = 5;
+
+/// 难度等级
+///
+/// 由玩家在主菜单中选择，同时驱动缺口大小公式和滚动速度
+#[derive(Clone, Copy, PartialEq)]
+enum Difficulty {
+    /// 简单：缺口更宽，收窄更慢，滚动更慢
+    Easy,
+    /// 普通：与原始的固定难度曲线一致
+    Normal,
+    /// 困难：缺口更窄，收窄更快，滚动更快
+    Hard,
+}
+
+/// 由 `Difficulty` 派生出的具体数值配置
+struct DifficultyConfig {
+    /// 缺口大小的下限
+    min_gap: i32,
+    /// 得分为 0 时的缺口大小
+    base_gap: i32,
+    /// 缺口随分数收窄的速率：每 `rate` 分收窄 1 行
+    rate: i32,
+    /// 该难度下的帧持续时间（毫秒），越小滚动越快
+    frame_duration: f32,
+}
+
+impl Difficulty {
+    /// 返回该难度对应的具体数值配置
+    fn config(self) -> DifficultyConfig {
+        match self {
+            Difficulty::Easy => DifficultyConfig {
+                min_gap: 8,
+                base_gap: 24,
+                rate: 2,
+                frame_duration: 75.0,
+            },
+            Difficulty::Normal => DifficultyConfig {
+                min_gap: 2,
+                base_gap: 20,
+                rate: 1,
+                frame_duration: 60.0,
+            },
+            Difficulty::Hard => DifficultyConfig {
+                min_gap: 2,
+                base_gap: 16,
+                rate: 1,
+                frame_duration: 45.0,
+            },
+        }
+    }
+}
 
 /// 游戏模式枚举
 /// 表示游戏当前所处的状态
@@ -17,6 +83,8 @@ enum GameMode {
     Menu,
     /// 游戏进行中状态
     Playing,
+    /// 游戏暂停状态
+    Paused,
     /// 游戏结束状态
     End,
 }
@@ -29,6 +97,10 @@ struct Player {
     y: i32,
     /// 垂直速度
     velocity: f32,
+    /// 当前动画帧在 `DRAGON_FRAMES` 中的下标
+    frame: usize,
+    /// 累计的动画帧时间
+    frame_timer: f32,
 }
 
 impl Player {
@@ -45,6 +117,8 @@ impl Player {
             x,
             y,
             velocity: 0.0,
+            frame: 0,
+            frame_timer: 0.0,
         }
     }
 
@@ -53,13 +127,13 @@ impl Player {
     /// # 参数
     /// * `ctx` - BTerm 上下文，用于渲染
     fn render(&mut self, ctx: &mut BTerm) {
-        ctx.set(0, self.y, YELLOW, BLACK, to_cp437('@'))
+        ctx.set(0, self.y, YELLOW, BLACK, DRAGON_FRAMES[self.frame])
     }
 
     /// 应用重力并移动玩家
     ///
-    /// 更新玩家的速度和位置，模拟重力效果
-    fn gravity_and_move(&mut self) {
+    /// 更新玩家的速度和位置，模拟重力效果，并推进扇翅动画
+    fn gravity_and_move(&mut self, frame_time_ms: f32) {
         if self.velocity < 2.0 {
             self.velocity += 0.2;
         }
@@ -68,13 +142,21 @@ impl Player {
         if self.y < 0 {
             self.y = 0;
         }
+
+        self.frame_timer += frame_time_ms;
+        if self.frame_timer > FLAP_FRAME_DURATION {
+            self.frame_timer = 0.0;
+            self.frame = (self.frame + 1) % DRAGON_FRAMES.len();
+        }
     }
 
     /// 玩家拍打翅膀
     ///
-    /// 给予玩家向上的速度
+    /// 给予玩家向上的速度，并将扇翅动画重置到第一帧
     fn flap(&mut self) {
         self.velocity = -2.0;
+        self.frame = 0;
+        self.frame_timer = 0.0;
     }
 }
 
@@ -86,6 +168,8 @@ struct Obstacle {
     gap_y: i32,
     /// 缺口大小
     size: i32,
+    /// 玩家是否已经越过该障碍物（用于计分去重）
+    passed: bool,
 }
 
 impl Obstacle {
@@ -94,15 +178,18 @@ impl Obstacle {
     /// # 参数
     /// * `x` - 障碍物的 X 坐标
     /// * `score` - 当前游戏得分，影响障碍物难度
+    /// * `difficulty` - 当前难度，决定缺口大小公式
     ///
     /// # 返回值
     /// 返回一个新的 Obstacle 实例
-    fn new(x: i32, score: i32) -> Self {
+    fn new(x: i32, score: i32, difficulty: Difficulty) -> Self {
+        let config = difficulty.config();
         let mut random = RandomNumberGenerator::new();
         Self {
             x,
             gap_y: random.range(10, 40),
-            size: i32::max(2, 20 - score),
+            size: i32::max(config.min_gap, config.base_gap - score / config.rate),
+            passed: false,
         }
     }
 
@@ -142,18 +229,149 @@ impl Obstacle {
     }
 }
 
+/// 音效类型
+///
+/// 标识一次 `Sounds::play` 调用应当播放哪个音效剪辑
+enum SoundEffect {
+    /// 拍打翅膀
+    Flap,
+    /// 得分
+    Score,
+    /// 死亡
+    Death,
+}
+
+/// 音效子系统
+///
+/// 在 `State::new` 中加载一次，集中负责所有音效剪辑的解码与播放，
+/// 避免播放逻辑散落在渲染循环各处
+#[cfg(feature = "audio")]
+struct Sounds {
+    /// 输出流句柄必须保持存活，否则音频设备会被提前释放；
+    /// 没有可用音频设备时为 `None`
+    _stream: Option<OutputStream>,
+    /// 用于播放解码后音频的句柄；没有可用音频设备时为 `None`
+    handle: Option<OutputStreamHandle>,
+    /// 拍打翅膀音效的原始 WAV 数据
+    flap: Vec<u8>,
+    /// 得分音效的原始 WAV 数据
+    score: Vec<u8>,
+    /// 死亡音效的原始 WAV 数据
+    death: Vec<u8>,
+    /// 主静音开关
+    muted: bool,
+}
+
+#[cfg(feature = "audio")]
+impl Sounds {
+    /// 加载全部音效剪辑，并尝试打开默认音频输出设备
+    ///
+    /// 在没有可用音频设备的环境（CI、容器、无声卡的无头主机）中，
+    /// `OutputStream::try_default` 会失败；这种情况下退化为静音，
+    /// 而不是让整个游戏在启动时崩溃
+    ///
+    /// # 返回值
+    /// 返回一个新的 Sounds 实例
+    fn new() -> Self {
+        let output = OutputStream::try_default();
+        let muted = output.is_err();
+        let (stream, handle) = match output {
+            Ok((stream, handle)) => (Some(stream), Some(handle)),
+            Err(_) => (None, None),
+        };
+        Sounds {
+            _stream: stream,
+            handle,
+            flap: include_bytes!("../resources/flap.wav").to_vec(),
+            score: include_bytes!("../resources/score.wav").to_vec(),
+            death: include_bytes!("../resources/death.wav").to_vec(),
+            muted,
+        }
+    }
+
+    /// 播放指定音效
+    ///
+    /// 静音开启（包括没有可用音频设备的情况）时直接返回，不做任何事
+    ///
+    /// # 参数
+    /// * `effect` - 要播放的音效类型
+    fn play(&self, effect: SoundEffect) {
+        if self.muted {
+            return;
+        }
+        let Some(handle) = &self.handle else {
+            return;
+        };
+        let clip = match effect {
+            SoundEffect::Flap => &self.flap,
+            SoundEffect::Score => &self.score,
+            SoundEffect::Death => &self.death,
+        };
+        if let Ok(decoder) = Decoder::new(Cursor::new(clip.clone())) {
+            let _ = handle.play_raw(decoder.convert_samples());
+        }
+    }
+
+    /// 切换主静音开关
+    fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+}
+
+/// 音效子系统（空实现）
+///
+/// 用于 WebAssembly（没有 `rodio`/`cpal` 可用）以及没有启用 `audio`
+/// feature 的构建（例如 `console`，专为没装 ALSA 开发包的无头主机准备，
+/// 默认不拉取音频依赖）。这里只保留一个静音开关，`play` 不做任何事；
+/// 调用方（`State`）不需要区分后端
+#[cfg(not(feature = "audio"))]
+struct Sounds {
+    /// 主静音开关
+    muted: bool,
+}
+
+#[cfg(not(feature = "audio"))]
+impl Sounds {
+    /// 返回一个已静音的 Sounds 实例，不打开任何音频设备
+    ///
+    /// # 返回值
+    /// 返回一个新的 Sounds 实例
+    fn new() -> Self {
+        Sounds { muted: true }
+    }
+
+    /// 空实现：未启用 `audio` feature 时不播放音效
+    ///
+    /// # 参数
+    /// * `effect` - 要播放的音效类型
+    fn play(&self, _effect: SoundEffect) {}
+
+    /// 切换主静音开关
+    fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+}
+
 /// 游戏状态结构体
 struct State {
     /// 玩家实例
     player: Player,
     /// 累计的帧时间
     frame_time: f32,
-    /// 当前障碍物
-    obstacle: Obstacle,
+    /// 当前在屏幕上滚动的障碍物队列
+    obstacles: VecDeque<Obstacle>,
     /// 当前游戏模式
     mode: GameMode,
     /// 当前得分
     score: i32,
+    /// 音效子系统
+    sounds: Sounds,
+    /// 玩家姓名，启动时读取一次
+    player_name: String,
+    /// 历史最高分榜
+    high_scores: HighScores,
+    /// 当前选择的难度
+    difficulty: Difficulty,
 }
 
 impl GameState for State {
@@ -165,6 +383,7 @@ impl GameState for State {
         match self.mode {
             GameMode::Menu => self.main_menu(ctx),
             GameMode::Playing => self.play(ctx),
+            GameMode::Paused => self.paused(ctx),
             GameMode::End => self.dead(ctx),
         }
     }
@@ -173,15 +392,22 @@ impl GameState for State {
 impl State {
     /// 创建新游戏状态
     ///
+    /// # 参数
+    /// * `player_name` - 启动时从标准输入读取的玩家姓名
+    ///
     /// # 返回值
     /// 返回一个初始化的 State 实例
-    fn new() -> Self {
+    fn new(player_name: String) -> Self {
         State {
             player: Player::new(5, 25),
             frame_time: 0.0,
-            obstacle: Obstacle::new(SCREEN_WIDTH, 0),
+            obstacles: VecDeque::from([Obstacle::new(SCREEN_WIDTH, 0, Difficulty::Normal)]),
             mode: GameMode::Menu,
             score: 0,
+            sounds: Sounds::new(),
+            player_name,
+            high_scores: HighScores::load(),
+            difficulty: Difficulty::Normal,
         }
     }
 
@@ -194,10 +420,34 @@ impl State {
         ctx.print_centered(5, "Welcome to Flappy Dragon!");
         ctx.print_centered(8, "(P) Play Game");
         ctx.print_centered(9, "(Q) Quit Game");
+        ctx.print_centered(
+            11,
+            if self.sounds.muted {
+                "(M) Unmute Sound"
+            } else {
+                "(M) Mute Sound"
+            },
+        );
+        ctx.print_centered(
+            12,
+            format!(
+                "(1) Easy  (2) Normal  (3) Hard  -- Difficulty: {}",
+                match self.difficulty {
+                    Difficulty::Easy => "Easy",
+                    Difficulty::Normal => "Normal",
+                    Difficulty::Hard => "Hard",
+                }
+            ),
+        );
+        self.render_high_scores(ctx, 14);
         if let Some(key) = ctx.key {
             match key {
                 VirtualKeyCode::P => self.restart(),
                 VirtualKeyCode::Q => ctx.quitting = true,
+                VirtualKeyCode::M => self.sounds.toggle_mute(),
+                VirtualKeyCode::Key1 => self.difficulty = Difficulty::Easy,
+                VirtualKeyCode::Key2 => self.difficulty = Difficulty::Normal,
+                VirtualKeyCode::Key3 => self.difficulty = Difficulty::Hard,
                 _ => {}
             }
         }
@@ -209,37 +459,92 @@ impl State {
     /// * `ctx` - BTerm 上下文
     fn play(&mut self, ctx: &mut BTerm) {
         ctx.cls_bg(NAVY);
+        let frame_duration = self.difficulty.config().frame_duration;
         self.frame_time += ctx.frame_time_ms;
-        if self.frame_time > FRAME_DURATION {
+        if self.frame_time > frame_duration {
             self.frame_time = 0.0;
-            self.player.gravity_and_move();
+            self.player.gravity_and_move(frame_duration);
         }
-        if let Some(VirtualKeyCode::Space) = ctx.key {
-            self.player.flap();
+        if let Some(key) = ctx.key {
+            match key {
+                VirtualKeyCode::Space => {
+                    self.player.flap();
+                    self.sounds.play(SoundEffect::Flap);
+                }
+                VirtualKeyCode::P => self.mode = GameMode::Paused,
+                _ => {}
+            }
         }
         self.player.render(ctx);
         ctx.print(0, 0, "Press Space to flap");
-        ctx.print(0, 1, &format!("Score: {}", self.score));
+        ctx.print(0, 1, format!("Score: {}", self.score));
         ctx.print(
             0,
             2,
-            &format!("Player({}, {})", self.player.x, self.player.y),
-        );
-        ctx.print(
-            0,
-            3,
-            &format!("Obstacle({}, {})", self.obstacle.x, self.obstacle.gap_y),
+            format!("Player({}, {})", self.player.x, self.player.y),
         );
-        self.obstacle.render(ctx, self.player.x);
-        if self.player.x > self.obstacle.x {
-            self.score += 1;
-            self.obstacle = Obstacle::new(self.player.x + SCREEN_WIDTH, self.score);
+        let furthest_x = self
+            .obstacles
+            .back()
+            .map(|obstacle| obstacle.x)
+            .unwrap_or(self.player.x);
+        if furthest_x - self.player.x < SPAWN_THRESHOLD {
+            self.obstacles.push_back(Obstacle::new(
+                furthest_x + OBSTACLE_SPACING,
+                self.score,
+                self.difficulty,
+            ));
+        }
+        while matches!(self.obstacles.front(), Some(obstacle) if obstacle.x < self.player.x - SCREEN_WIDTH)
+        {
+            self.obstacles.pop_front();
         }
-        if self.player.y > SCREEN_HEIGHT || self.obstacle.hit_obstacle(&self.player) {
+
+        let mut hit = false;
+        for obstacle in self.obstacles.iter_mut() {
+            obstacle.render(ctx, self.player.x);
+            if !obstacle.passed && self.player.x > obstacle.x {
+                obstacle.passed = true;
+                self.score += 1;
+                self.sounds.play(SoundEffect::Score);
+            }
+            hit |= obstacle.hit_obstacle(&self.player);
+        }
+        if self.player.y > SCREEN_HEIGHT || hit {
+            self.sounds.play(SoundEffect::Death);
+            self.high_scores.record(&self.player_name, self.score);
             self.mode = GameMode::End;
         }
     }
 
+    /// 在指定行开始渲染历史最高分榜
+    ///
+    /// # 参数
+    /// * `ctx` - BTerm 上下文
+    /// * `start_y` - 标题所在的行号
+    fn render_high_scores(&self, ctx: &mut BTerm, start_y: i32) {
+        ctx.print_centered(start_y, "High Scores");
+        for (i, entry) in self.high_scores.top(HIGH_SCORE_COUNT).iter().enumerate() {
+            ctx.print_centered(
+                start_y + 1 + i as i32,
+                format!("{}. {} - {}", i + 1, entry.name, entry.score),
+            );
+        }
+    }
+
+    /// 处理游戏暂停状态
+    ///
+    /// 冻结帧时间累积和玩家/障碍物模拟，直到玩家再次按下 P 键恢复游戏
+    ///
+    /// # 参数
+    /// * `ctx` - BTerm 上下文
+    fn paused(&mut self, ctx: &mut BTerm) {
+        ctx.print_centered(5, "Paused -- press P to resume");
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.mode = GameMode::Playing;
+        }
+    }
+
     /// 处理游戏结束状态
     ///
     /// # 参数
@@ -247,9 +552,10 @@ impl State {
     fn dead(&mut self, ctx: &mut BTerm) {
         ctx.cls();
         ctx.print_centered(5, "You are dead!");
-        ctx.print_centered(6, &format!("You earned {} points", self.score));
+        ctx.print_centered(6, format!("You earned {} points", self.score));
         ctx.print_centered(8, "(P) Play Again");
         ctx.print_centered(9, "(Q) Quit Game");
+        self.render_high_scores(ctx, 11);
         if let Some(key) = ctx.key {
             match key {
                 VirtualKeyCode::P => self.restart(),
@@ -265,22 +571,76 @@ impl State {
     fn restart(&mut self) {
         self.player = Player::new(5, 25);
         self.frame_time = 0.0;
-        self.obstacle = Obstacle::new(SCREEN_WIDTH, 0);
+        self.obstacles = VecDeque::from([Obstacle::new(SCREEN_WIDTH, 0, self.difficulty)]);
         self.mode = GameMode::Playing;
         self.score = 0;
     }
 }
 
-/// 程序入口点
+/// 在程序启动时从标准输入读取玩家姓名
+///
+/// 复用 `common::read_name`（`treehouse` 的姓名门禁示例也使用它），
+/// 而不是另外拷贝一份读取逻辑
+///
+/// # 返回值
+/// 返回玩家输入的姓名；输入为空时返回 "Anonymous"
+fn prompt_player_name() -> String {
+    println!("Welcome to Flappy Dragon! What's your name?");
+    let name = common::read_name();
+    if name.is_empty() {
+        "Anonymous".to_string()
+    } else {
+        name
+    }
+}
+
+/// 构建原生 OpenGL 窗口后端的上下文
+///
+/// 由 `native` cargo feature 启用，是默认的桌面渲染路径
+#[cfg(feature = "native")]
+fn build_context() -> BResult<BTerm> {
+    BTermBuilder::simple(SCREEN_WIDTH, SCREEN_HEIGHT)?
+        .with_title("Flappy Dragon")
+        .build()
+}
+
+/// 构建纯文本终端后端的上下文
+///
+/// 由 `console` cargo feature 启用，用于没有 GPU/窗口系统的环境。
+/// 具体后端（curses）由 bracket-lib 的 feature 决定，这里不需要
+/// 额外指定平台相关参数
+#[cfg(feature = "console")]
+fn build_context() -> BResult<BTerm> {
+    BTermBuilder::simple(SCREEN_WIDTH, SCREEN_HEIGHT)?
+        .with_title("Flappy Dragon")
+        .build()
+}
+
+/// 程序入口点（原生 / 纯文本后端）
+///
+/// 读取玩家姓名，创建游戏窗口并启动游戏循环。WebAssembly 构建没有
+/// 标准输入，因此姓名提示只在这个入口点运行
+///
+/// # 返回值
+/// 返回 BError，表示游戏运行状态
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> BError {
+    let player_name = prompt_player_name();
+    let context = build_context()?;
+    main_loop(context, State::new(player_name))
+}
+
+/// 程序入口点（WebAssembly 后端）
 ///
-/// 创建游戏窗口并启动游戏循环
+/// 由 `web` cargo feature 启用。浏览器环境没有 `stdin`，所以这里跳过
+/// `prompt_player_name` 并使用一个占位姓名；核心的游戏模式逻辑不变
 ///
 /// # 返回值
 /// 返回 BError，表示游戏运行状态
+#[cfg(target_arch = "wasm32")]
 fn main() -> BError {
-    let context = BTermBuilder::simple(SCREEN_WIDTH, SCREEN_HEIGHT)
-        .unwrap()
+    let context = BTermBuilder::simple(SCREEN_WIDTH, SCREEN_HEIGHT)?
         .with_title("Flappy Dragon")
         .build()?;
-    main_loop(context, State::new())
+    main_loop(context, State::new("Player".to_string()))
 }